@@ -0,0 +1,392 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A Merkle Mountain Range (MMR) over a chain's confirmed-block hashes.
+//!
+//! The MMR lets a light client verify that a block returned by a validator
+//! actually belongs to the chain's canonical history, without downloading the
+//! whole certificate stream. Validators maintain the structure incrementally
+//! as blocks are confirmed; clients only need to persist the small root hash
+//! and the proofs they are handed.
+//!
+//! The forest is a set of perfect binary trees ("peaks") whose sizes are the
+//! 1-bits of the leaf count, ordered from the largest (oldest) peak to the
+//! smallest (most recently completed) one. The root is the "bagging of
+//! peaks": the peak hashes folded right-to-left with `hash(acc || peak)`.
+//!
+//! This module only implements the data structure (append, prove, verify,
+//! tested below). This series wires `RpcMessage::ProveBlockInclusion` and
+//! `BlockInclusionProofResponse` onto it (see `message.rs`), but no
+//! validator yet maintains an [`Mmr`] per chain or answers the request with
+//! a real proof; that dispatcher-side integration is still to do.
+
+use linera_base::crypto::{BcsSignable, CryptoHash};
+use serde::{Deserialize, Serialize};
+
+/// An internal MMR node, hashed as `hash(left || right)`.
+#[derive(Serialize, Deserialize)]
+struct MmrNode(CryptoHash, CryptoHash);
+
+impl BcsSignable for MmrNode {}
+
+/// A leaf node, hashed from the confirmed block's own hash.
+#[derive(Serialize, Deserialize)]
+struct MmrLeaf(CryptoHash);
+
+impl BcsSignable for MmrLeaf {}
+
+/// The well-known root of an MMR with no leaves.
+#[derive(Serialize, Deserialize)]
+struct MmrEmpty;
+
+impl BcsSignable for MmrEmpty {}
+
+fn combine(left: &CryptoHash, right: &CryptoHash) -> CryptoHash {
+    CryptoHash::new(&MmrNode(*left, *right))
+}
+
+fn bag_peaks(peaks: &[CryptoHash]) -> CryptoHash {
+    let mut iter = peaks.iter().rev();
+    let Some(first) = iter.next() else {
+        return CryptoHash::new(&MmrEmpty);
+    };
+    let mut acc = *first;
+    for peak in iter {
+        acc = combine(&acc, peak);
+    }
+    acc
+}
+
+/// A hash on the path from a leaf to the root of its containing peak, together
+/// with which side of its parent it sits on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(with_testing, derive(Eq, PartialEq))]
+pub struct MmrSibling {
+    /// The sibling's hash.
+    pub hash: CryptoHash,
+    /// Whether the sibling is the right child of their shared parent (i.e. the
+    /// node being proven is the left child).
+    pub is_right: bool,
+}
+
+/// A perfect binary tree forming a single peak of the [`Mmr`] forest.
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(with_testing, derive(Debug, Eq, PartialEq))]
+enum PeakTree {
+    Leaf(CryptoHash),
+    Node {
+        hash: CryptoHash,
+        left: Box<PeakTree>,
+        right: Box<PeakTree>,
+    },
+}
+
+impl PeakTree {
+    fn leaf(block_hash: CryptoHash) -> Self {
+        PeakTree::Leaf(CryptoHash::new(&MmrLeaf(block_hash)))
+    }
+
+    fn hash(&self) -> CryptoHash {
+        match self {
+            PeakTree::Leaf(hash) => *hash,
+            PeakTree::Node { hash, .. } => *hash,
+        }
+    }
+
+    fn height(&self) -> u32 {
+        match self {
+            PeakTree::Leaf(_) => 0,
+            PeakTree::Node { left, .. } => left.height() + 1,
+        }
+    }
+
+    fn merge(left: Self, right: Self) -> Self {
+        PeakTree::Node {
+            hash: combine(&left.hash(), &right.hash()),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Collects the sibling path from the leaf at `index` (0-based, relative to
+    /// this peak's own leaves) up to this peak's root, in leaf-to-root order.
+    fn prove(&self, index: u64) -> Vec<MmrSibling> {
+        match self {
+            PeakTree::Leaf(_) => Vec::new(),
+            PeakTree::Node { left, right, .. } => {
+                let half = 1u64 << left.height();
+                if index < half {
+                    let mut path = left.prove(index);
+                    path.push(MmrSibling {
+                        hash: right.hash(),
+                        is_right: true,
+                    });
+                    path
+                } else {
+                    let mut path = right.prove(index - half);
+                    path.push(MmrSibling {
+                        hash: left.hash(),
+                        is_right: false,
+                    });
+                    path
+                }
+            }
+        }
+    }
+}
+
+/// An append-only Merkle Mountain Range over confirmed-block hashes.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(with_testing, derive(Debug, Eq, PartialEq))]
+pub struct Mmr {
+    /// The peaks of the forest, ordered from largest (oldest) to smallest
+    /// (most recently completed).
+    peaks: Vec<PeakTree>,
+    /// The total number of leaves appended so far.
+    leaf_count: u64,
+}
+
+impl Mmr {
+    /// Appends a new confirmed-block hash as the next leaf.
+    pub fn append(&mut self, block_hash: CryptoHash) {
+        let mut tree = PeakTree::leaf(block_hash);
+        while matches!(self.peaks.last(), Some(peak) if peak.height() == tree.height()) {
+            let peak = self.peaks.pop().expect("checked by the match above");
+            tree = PeakTree::merge(peak, tree);
+        }
+        self.peaks.push(tree);
+        self.leaf_count += 1;
+    }
+
+    /// The number of leaves (confirmed blocks) in the MMR.
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// The MMR root, i.e. the bagging of all current peaks.
+    pub fn root(&self) -> CryptoHash {
+        let peak_hashes: Vec<_> = self.peaks.iter().map(PeakTree::hash).collect();
+        bag_peaks(&peak_hashes)
+    }
+
+    /// Produces an inclusion proof for the leaf at the given height, or `None`
+    /// if `leaf_index >= self.leaf_count()`.
+    pub fn prove(&self, leaf_index: u64) -> Option<MmrProof> {
+        if leaf_index >= self.leaf_count {
+            return None;
+        }
+        let mut start = 0u64;
+        for (peak_index, peak) in self.peaks.iter().enumerate() {
+            let size = 1u64 << peak.height();
+            if leaf_index < start + size {
+                let siblings = peak.prove(leaf_index - start);
+                let other_peaks = self
+                    .peaks
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| *index != peak_index)
+                    .map(|(_, peak)| peak.hash())
+                    .collect();
+                return Some(MmrProof {
+                    leaf_index,
+                    leaf_count: self.leaf_count,
+                    siblings,
+                    peak_index,
+                    other_peaks,
+                });
+            }
+            start += size;
+        }
+        None
+    }
+}
+
+/// A proof that a leaf at a given height belongs to an [`Mmr`] with a known root.
+///
+/// `leaf_index`, `peak_index` and each [`MmrSibling::is_right`] are the prover's
+/// own bookkeeping, carried on the wire so a client doesn't have to recompute
+/// them; [`MmrProof::verify`] does not trust any of them; see its doc comment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(with_testing, derive(Eq, PartialEq))]
+pub struct MmrProof {
+    /// The height of the confirmed block this proof is for, as claimed by the
+    /// prover. Not read by `verify`; pass the height you actually asked for.
+    pub leaf_index: u64,
+    /// The total leaf count of the MMR the proof was generated against.
+    pub leaf_count: u64,
+    /// The sibling hashes on the path from the leaf up to its containing peak.
+    pub siblings: Vec<MmrSibling>,
+    /// The position of the proven leaf's peak among all of the MMR's peaks, as
+    /// claimed by the prover. Not read by `verify`, which rederives it.
+    pub peak_index: usize,
+    /// The hashes of all peaks other than the one containing the leaf, in order.
+    pub other_peaks: Vec<CryptoHash>,
+}
+
+impl MmrProof {
+    /// The sizes of the peaks of an MMR with `leaf_count` leaves, largest
+    /// (oldest) first: one entry per set bit of `leaf_count`, from the most
+    /// significant bit down. This matches the order `Mmr::peaks` is built in.
+    fn peak_sizes(leaf_count: u64) -> Vec<u64> {
+        (0..u64::BITS)
+            .rev()
+            .filter(|bit| leaf_count & (1 << bit) != 0)
+            .map(|bit| 1u64 << bit)
+            .collect()
+    }
+
+    /// Verifies that the leaf at `leaf_index` hashes to `block_hash` and is
+    /// included in the chain committed to by `root`.
+    ///
+    /// `leaf_index` must be the height the caller actually asked about. It is
+    /// deliberately a parameter rather than read from `self.leaf_index`: the
+    /// peer that produced this proof could relabel `self.leaf_index` (and
+    /// `self.peak_index`) to claim a genuine proof of some other leaf is a
+    /// proof of this one. Instead, the peak containing `leaf_index` and the
+    /// expected left/right path to it are rederived here from `self.leaf_count`
+    /// and checked against the supplied siblings, so a mislabeled proof fails
+    /// rather than silently verifying.
+    pub fn verify(&self, leaf_index: u64, block_hash: CryptoHash, root: CryptoHash) -> bool {
+        let peak_sizes = Self::peak_sizes(self.leaf_count);
+        if self.other_peaks.len() + 1 != peak_sizes.len() {
+            return false;
+        }
+        let mut start = 0u64;
+        let mut located = None;
+        for (peak_index, &size) in peak_sizes.iter().enumerate() {
+            if leaf_index < start + size {
+                located = Some((peak_index, size));
+                break;
+            }
+            start += size;
+        }
+        let Some((peak_index, size)) = located else {
+            return false;
+        };
+        let height = size.trailing_zeros() as usize;
+        if self.siblings.len() != height {
+            return false;
+        }
+        let relative_index = leaf_index - start;
+        let mut acc = CryptoHash::new(&MmrLeaf(block_hash));
+        for (level, sibling) in self.siblings.iter().enumerate() {
+            acc = if (relative_index >> level) & 1 == 0 {
+                combine(&acc, &sibling.hash)
+            } else {
+                combine(&sibling.hash, &acc)
+            };
+        }
+        let mut peaks = self.other_peaks.clone();
+        peaks.insert(peak_index, acc);
+        bag_peaks(&peaks) == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A distinct, per-index "block hash" for test fixtures, so that leaves
+    /// never collide with each other or with the [`MmrLeaf`]/[`MmrNode`]
+    /// domains.
+    #[derive(Serialize, Deserialize)]
+    struct TestBlock(u64);
+
+    impl BcsSignable for TestBlock {}
+
+    fn block_hash(index: u64) -> CryptoHash {
+        CryptoHash::new(&TestBlock(index))
+    }
+
+    /// A non-power-of-two leaf count exercises a forest with more than one
+    /// peak, so proofs must cross from a leaf's own peak into the bagging of
+    /// the others.
+    #[test]
+    fn prove_and_verify_round_trip_for_every_leaf() {
+        const LEAF_COUNT: u64 = 5;
+        let mut mmr = Mmr::default();
+        let hashes: Vec<_> = (0..LEAF_COUNT).map(block_hash).collect();
+        for hash in &hashes {
+            mmr.append(*hash);
+        }
+        assert_eq!(mmr.leaf_count(), LEAF_COUNT);
+        let root = mmr.root();
+
+        for index in 0..LEAF_COUNT {
+            let proof = mmr.prove(index).expect("index is within leaf_count");
+            assert_eq!(proof.leaf_index, index);
+            assert_eq!(proof.leaf_count, LEAF_COUNT);
+            assert!(
+                proof.verify(index, hashes[index as usize], root),
+                "proof for leaf {index} should verify against the real root"
+            );
+        }
+    }
+
+    #[test]
+    fn prove_returns_none_past_the_leaf_count() {
+        let mut mmr = Mmr::default();
+        mmr.append(block_hash(0));
+        assert!(mmr.prove(1).is_none());
+    }
+
+    #[test]
+    fn verify_fails_for_the_wrong_leaf_hash() {
+        let mut mmr = Mmr::default();
+        for index in 0..6 {
+            mmr.append(block_hash(index));
+        }
+        let root = mmr.root();
+        let proof = mmr.prove(2).unwrap();
+        assert!(!proof.verify(2, block_hash(99), root));
+    }
+
+    #[test]
+    fn verify_fails_after_corrupting_a_sibling() {
+        let mut mmr = Mmr::default();
+        for index in 0..6 {
+            mmr.append(block_hash(index));
+        }
+        let root = mmr.root();
+        let mut proof = mmr.prove(2).unwrap();
+        assert!(!proof.siblings.is_empty());
+        proof.siblings[0].hash = block_hash(999);
+        assert!(!proof.verify(2, block_hash(2), root));
+    }
+
+    #[test]
+    fn verify_fails_after_corrupting_a_peak() {
+        let mut mmr = Mmr::default();
+        for index in 0..6 {
+            mmr.append(block_hash(index));
+        }
+        let root = mmr.root();
+        let mut proof = mmr.prove(2).unwrap();
+        assert!(!proof.other_peaks.is_empty());
+        proof.other_peaks[0] = block_hash(999);
+        assert!(!proof.verify(2, block_hash(2), root));
+    }
+
+    /// A genuine proof for one leaf, relabeled with another leaf's index and
+    /// hash, must not verify: `verify` takes the expected index as a
+    /// parameter rather than trusting `proof.leaf_index`, specifically to
+    /// catch this.
+    #[test]
+    fn verify_fails_for_a_relabeled_leaf_index() {
+        let mut mmr = Mmr::default();
+        for index in 0..6 {
+            mmr.append(block_hash(index));
+        }
+        let root = mmr.root();
+        let proof = mmr.prove(2).unwrap();
+        for other_index in 0..6 {
+            if other_index == 2 {
+                continue;
+            }
+            assert!(
+                !proof.verify(other_index, block_hash(other_index), root),
+                "a proof for leaf 2 should not verify as leaf {other_index}"
+            );
+        }
+    }
+}